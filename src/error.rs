@@ -0,0 +1,115 @@
+//! Error types returned by the blocking and fallible send/receive APIs.
+
+use core::fmt;
+
+/// Error returned by [`Receiver::recv`] when every [`Sender`] has been
+/// dropped and there are no more values left to receive.
+///
+/// [`Receiver::recv`]: struct.Receiver.html#method.recv
+/// [`Sender`]: struct.Sender.html
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+#[cfg(feature = "std")]
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty and disconnected channel")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecvError {}
+
+/// Error returned by [`Receiver::recv_timeout`].
+///
+/// [`Receiver::recv_timeout`]: struct.Receiver.html#method.recv_timeout
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// No value arrived before the timeout elapsed.
+    Timeout,
+    /// Every [`Sender`] was dropped before a value arrived.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    Disconnected,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "timed out waiting on channel"),
+            RecvTimeoutError::Disconnected => {
+                write!(f, "receiving on an empty and disconnected channel")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecvTimeoutError {}
+
+/// Error returned by [`Receiver::try_recv`] when no value is ready yet.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No value has been sent yet, but at least one [`Sender`] is still
+    /// alive, so one may still arrive.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    Empty,
+    /// No value is available, and every [`Sender`] has been dropped, so
+    /// none ever will be.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receiving on an empty channel"),
+            TryRecvError::Disconnected => {
+                write!(f, "receiving on an empty and disconnected channel")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryRecvError {}
+
+/// Error returned by [`Sender::send`]/[`Sender::send_all`] once every
+/// [`Receiver`] has been dropped, so the value would never be read.
+///
+/// The value that could not be sent is returned so the caller can recover
+/// it instead of it being silently dropped.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+/// [`Sender::send_all`]: struct.Sender.html#method.send_all
+/// [`Receiver`]: struct.Receiver.html
+pub struct SendError<T>(pub T);
+
+impl<T> SendError<T> {
+    /// Unwraps the value that failed to send.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a broadcast channel with no receivers left".fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> std::error::Error for SendError<T> {}
@@ -0,0 +1,105 @@
+//! Safe reclamation for nodes unlinked from [`BroadcastChannel`](crate::sync)'s
+//! list while other threads may still be dereferencing them.
+//!
+//! Each [`Sender`](crate::Sender)/[`Receiver`](crate::Receiver) owns one
+//! [`HazardSlot`], registered with the channel's [`HazardRegistry`] for as
+//! long as the handle is alive. Before dereferencing a node it doesn't
+//! already hold a live reference to, a thread publishes that node's address
+//! into its slot; [`HazardRegistry::retire`] only actually frees a node once
+//! a scan of every registered slot finds none still pointing at it,
+//! otherwise it defers the free to a later `retire` call.
+
+use crate::port::{Arc, AtomicPtr, Box, Ordering, Vec};
+use crate::spin::SpinMutex;
+use core::ptr;
+
+pub(crate) struct HazardSlot<T>(AtomicPtr<T>);
+
+impl<T> HazardSlot<T> {
+    fn new() -> Self {
+        Self(AtomicPtr::new(ptr::null_mut()))
+    }
+
+    /// Publishes `ptr` as about to be dereferenced, so a concurrent
+    /// `retire` of it defers the free instead of racing this read.
+    pub(crate) fn protect(&self, ptr: *mut T) {
+        self.0.store(ptr, Ordering::SeqCst);
+    }
+
+    /// Withdraws the protection published by `protect`, once `ptr` is no
+    /// longer being dereferenced through this slot.
+    pub(crate) fn clear(&self) {
+        self.0.store(ptr::null_mut(), Ordering::SeqCst);
+    }
+
+    fn get(&self) -> *mut T {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+pub(crate) struct HazardRegistry<T> {
+    slots: SpinMutex<Vec<Arc<HazardSlot<T>>>>,
+    retired: SpinMutex<Vec<*mut T>>,
+}
+
+// The slots/retired nodes are only ever touched through the spinlocks above,
+// so the registry is safe to share across threads as long as `T` is.
+unsafe impl<T: Send> Send for HazardRegistry<T> {}
+unsafe impl<T: Send> Sync for HazardRegistry<T> {}
+
+impl<T> HazardRegistry<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: SpinMutex::new(Vec::new()),
+            retired: SpinMutex::new(Vec::new()),
+        }
+    }
+
+    /// Allocates a new slot for a handle to protect pointers with, and
+    /// registers it so `retire` knows to check it before freeing anything.
+    pub(crate) fn register(&self) -> Arc<HazardSlot<T>> {
+        let slot = Arc::new(HazardSlot::new());
+        self.slots.lock().push(slot.clone());
+        slot
+    }
+
+    /// Drops `slot` from the registry once its owning handle is gone.
+    pub(crate) fn deregister(&self, slot: &Arc<HazardSlot<T>>) {
+        self.slots
+            .lock()
+            .retain(|registered| !Arc::ptr_eq(registered, slot));
+    }
+
+    /// Retires `node`: frees it immediately if no hazard slot currently
+    /// protects it, otherwise holds onto it and tries again on the next
+    /// call (including from other threads retiring other nodes).
+    ///
+    /// # Safety
+    /// `node` must have come from `Box::into_raw` and must not be
+    /// dereferenced by the caller after this call.
+    pub(crate) unsafe fn retire(&self, node: *mut T) {
+        self.retired.lock().push(node);
+        self.reclaim();
+    }
+
+    fn reclaim(&self) {
+        let mut retired = self.retired.lock();
+        if retired.is_empty() {
+            return;
+        }
+        let slots = self.slots.lock();
+        retired.retain(|&node| {
+            let protected = slots.iter().any(|slot| slot.get() == node);
+            if protected {
+                true
+            } else {
+                // SAFETY: `node` was pushed by `retire`, which requires it
+                // to have come from `Box::into_raw` and to no longer be in
+                // use, and we just confirmed no hazard slot still protects
+                // it.
+                drop(unsafe { Box::from_raw(node) });
+                false
+            }
+        });
+    }
+}
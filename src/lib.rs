@@ -11,15 +11,15 @@
 //! [`Sender`]: struct.Sender.html
 //! [`Receiver`]: struct.Receiver.html
 //!
-//! # Examples  
+//! # Examples
 //! Simple use:
 //! ```rust
 //! use broadcast_channel::broadcaster;
 //!
 //! # fn main() {
 //! let (tx, mut rx) = broadcaster();
-//! tx.send(1);
-//! assert_eq!(rx.next(), Some(1));   
+//! tx.send(1).unwrap();
+//! assert_eq!(rx.next(), Some(1));
 //! # }
 //! ```
 //! Threaded use:
@@ -30,7 +30,7 @@
 //! # fn main() {
 //! let (tx, mut rx) = broadcaster();
 //! let thread = thread::spawn(move || {
-//!     tx.send_all(0..10);
+//!     tx.send_all(0..10).unwrap();
 //! });
 //! thread.join();
 //! for (i, item) in rx.enumerate() {
@@ -38,5 +38,23 @@
 //! }
 //! # }
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+mod error;
+mod hazard;
+mod port;
+mod spin;
 mod sync;
+pub use error::*;
 pub use sync::*;
+
+#[cfg(feature = "std")]
+mod select;
+#[cfg(feature = "std")]
+pub use select::{Select, Selected};
+
+#[cfg(feature = "futures")]
+mod stream;
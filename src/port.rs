@@ -0,0 +1,40 @@
+//! Picks the allocation and atomic primitives the rest of the crate builds
+//! on, so the same channel code compiles under plain `std`, under
+//! `no_std` + `alloc`, with the `portable-atomic` backend for targets
+//! without native atomic instructions, and under `loom` for the
+//! concurrency tests (enabled via `cfg(loom)`, see `tests/loom.rs`).
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::boxed::Box;
+#[cfg(feature = "std")]
+pub(crate) use std::boxed::Box;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::vec::Vec;
+#[cfg(feature = "std")]
+pub(crate) use std::vec::Vec;
+
+#[cfg(all(not(loom), not(feature = "std")))]
+pub(crate) use alloc::sync::Arc;
+#[cfg(loom)]
+pub(crate) use loom::sync::Arc;
+#[cfg(all(not(loom), feature = "std"))]
+pub(crate) use std::sync::Arc;
+
+#[cfg(all(not(loom), not(feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+#[cfg(all(not(loom), feature = "portable-atomic"))]
+pub(crate) use portable_atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+/// Yields to the scheduler in a CAS retry loop. Under loom, spinning without
+/// ever yielding makes the model checker give up (indistinguishable from an
+/// algorithm that can't make progress); everywhere else this is just the
+/// usual `spin_loop` hint.
+pub(crate) fn spin_hint() {
+    #[cfg(loom)]
+    loom::thread::yield_now();
+    #[cfg(not(loom))]
+    core::hint::spin_loop();
+}
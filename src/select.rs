@@ -0,0 +1,231 @@
+//! Waiting on several heterogeneous [`Receiver`]s at once.
+//!
+//! [`Receiver`]: crate::Receiver
+
+use crate::Receiver;
+use std::any::Any;
+use std::sync::Arc;
+use std::task::{Wake, Waker};
+use std::thread::{self, Thread};
+
+/// Type-erased view of a registered [`Receiver<T>`] that [`Select`] needs:
+/// "is a value ready?", "has every sender gone away?", and "give me that
+/// value" (boxed, since `Select` can no longer know `T` once receivers of
+/// different types share one `Vec`).
+///
+/// [`Receiver<T>`]: crate::Receiver
+trait SelectHandle {
+    fn has_ready(&self) -> bool;
+    fn is_disconnected(&self) -> bool;
+    fn register_waker(&self, waker: Waker);
+    fn drain(&mut self) -> Option<Box<dyn Any>>;
+}
+
+impl<T: Send + Clone + 'static> SelectHandle for Receiver<T> {
+    fn has_ready(&self) -> bool {
+        Receiver::has_ready(self)
+    }
+
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+
+    fn register_waker(&self, waker: Waker) {
+        Receiver::register_waker(self, waker)
+    }
+
+    fn drain(&mut self) -> Option<Box<dyn Any>> {
+        self.try_recv()
+            .ok()
+            .map(|value| Box::new(value) as Box<dyn Any>)
+    }
+}
+
+/// The receiver a [`Select`] picked, paired with the value it drained.
+///
+/// Call [`downcast`](Selected::downcast) with the type of the `Receiver`
+/// that was registered at [`index`](Selected::index) to recover it.
+pub struct Selected {
+    index: usize,
+    value: Box<dyn Any>,
+}
+
+impl Selected {
+    /// The position (in registration order) of the `Receiver` this value
+    /// came from.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Recovers the value, or hands `self` back unchanged if `T` doesn't
+    /// match the `Receiver` registered at `self.index()`.
+    pub fn downcast<T: 'static>(self) -> Result<T, Self> {
+        match self.value.downcast::<T>() {
+            Ok(value) => Ok(*value),
+            Err(value) => Err(Self {
+                index: self.index,
+                value,
+            }),
+        }
+    }
+}
+
+/// Wakes a single parked thread, used to park [`Select::select`] on
+/// whichever registered channel's [`Waker`] list gets notified first.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Waits on several [`Receiver`]s of possibly different types at once,
+/// acting on whichever delivers first.
+///
+/// [`Receiver`]: crate::Receiver
+///
+/// # Examples
+/// ```rust
+/// use broadcast_channel::{broadcaster, Select};
+///
+/// let (tx1, mut rx1) = broadcaster();
+/// let (_tx2, mut rx2) = broadcaster::<&'static str>();
+/// tx1.send(1).unwrap();
+///
+/// let mut select = Select::new();
+/// select.add(&mut rx1);
+/// select.add(&mut rx2);
+///
+/// let selected = select.try_select().unwrap();
+/// assert_eq!(selected.index(), 0);
+/// assert_eq!(selected.downcast::<i32>().ok(), Some(1));
+/// ```
+#[derive(Default)]
+pub struct Select<'r> {
+    handles: Vec<&'r mut dyn SelectHandle>,
+}
+
+impl<'r> Select<'r> {
+    /// Creates an empty `Select` with no registered receivers.
+    pub fn new() -> Self {
+        Self {
+            handles: Vec::new(),
+        }
+    }
+
+    /// Registers `receiver`, returning its index for later reference.
+    pub fn add<T: Send + Clone + 'static>(&mut self, receiver: &'r mut Receiver<T>) -> usize {
+        self.handles.push(receiver);
+        self.handles.len() - 1
+    }
+
+    /// Scans every registered receiver once and drains the first one with
+    /// a value ready, without blocking.
+    pub fn try_select(&mut self) -> Option<Selected> {
+        let index = self.handles.iter().position(|handle| handle.has_ready())?;
+        let value = self.handles[index].drain()?;
+        Some(Selected { index, value })
+    }
+
+    /// Blocks the current thread until one of the registered receivers has
+    /// a value ready, or returns `None` once every one of them is both
+    /// disconnected and empty.
+    pub fn select(&mut self) -> Option<Selected> {
+        loop {
+            if let Some(selected) = self.try_select() {
+                return Some(selected);
+            }
+
+            if self.handles.iter().all(|handle| handle.is_disconnected()) {
+                // One more look: a final value could have raced the last
+                // sender disconnecting on one of the channels.
+                return self.try_select();
+            }
+
+            let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+            for handle in self.handles.iter() {
+                handle.register_waker(waker.clone());
+            }
+
+            // A value may have arrived between our scan above and
+            // registering the waker; re-check before parking so we don't
+            // miss it.
+            if let Some(selected) = self.try_select() {
+                return Some(selected);
+            }
+
+            thread::park();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::broadcaster;
+    use std::time::Duration;
+
+    #[test]
+    fn select_blocks_until_a_registered_receiver_has_something() {
+        let (tx1, mut rx1) = broadcaster::<i32>();
+        let (_tx2, mut rx2) = broadcaster::<&'static str>();
+
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx1.send(42).unwrap();
+        });
+
+        let mut select = Select::new();
+        select.add(&mut rx1);
+        select.add(&mut rx2);
+
+        let selected = select.select().unwrap();
+        assert_eq!(selected.index(), 0);
+        assert_eq!(selected.downcast::<i32>().ok(), Some(42));
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn select_wakes_on_whichever_receiver_is_sent_to_first() {
+        let (_tx1, mut rx1) = broadcaster::<i32>();
+        let (tx2, mut rx2) = broadcaster::<&'static str>();
+
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx2.send("hi").unwrap();
+        });
+
+        let mut select = Select::new();
+        select.add(&mut rx1);
+        select.add(&mut rx2);
+
+        let selected = select.select().unwrap();
+        assert_eq!(selected.index(), 1);
+        assert_eq!(selected.downcast::<&'static str>().ok(), Some("hi"));
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn select_returns_none_once_every_receiver_disconnects() {
+        let (tx1, mut rx1) = broadcaster::<i32>();
+        let (tx2, mut rx2) = broadcaster::<i32>();
+
+        let closer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            drop(tx1);
+            drop(tx2);
+        });
+
+        let mut select = Select::new();
+        select.add(&mut rx1);
+        select.add(&mut rx2);
+
+        assert!(select.select().is_none());
+        closer.join().unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+//! A minimal spinlock, independent of `std::sync::Mutex`, so the waker
+//! lists backing `poll_next`/`poll_ready` work the same with or without
+//! `std`. The blocking `recv`/`recv_timeout`/bounded-`send` paths still
+//! use `std::sync::{Mutex, Condvar}` directly where `std` is available,
+//! since parking a thread is meaningless without one.
+
+use crate::port::{spin_hint, AtomicBool, Ordering};
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+
+pub(crate) struct SpinMutex<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for SpinMutex<T> {}
+unsafe impl<T: Send> Sync for SpinMutex<T> {}
+
+impl<T> SpinMutex<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub(crate) fn lock(&self) -> SpinMutexGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            spin_hint();
+        }
+        SpinMutexGuard { lock: self }
+    }
+}
+
+pub(crate) struct SpinMutexGuard<'a, T> {
+    lock: &'a SpinMutex<T>,
+}
+
+impl<'a, T> Deref for SpinMutexGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<'a, T> DerefMut for SpinMutexGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<'a, T> Drop for SpinMutexGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::SeqCst);
+    }
+}
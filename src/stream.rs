@@ -0,0 +1,17 @@
+//! `futures::Stream` support for [`Receiver`].
+//!
+//! [`Receiver`]: ../struct.Receiver.html
+
+use crate::Receiver;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+impl<T: Send + Clone + 'static> futures_core::Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // `Receiver` holds no self-referential data, so it is `Unpin` and we
+        // can hand the inner `&mut` off to the inherent `poll_next`.
+        Pin::into_inner(self).poll_next(cx)
+    }
+}
@@ -1,10 +1,17 @@
 //! Channels to broadcast messages to all their receivers
 
-use std::marker::PhantomData;
-use std::ptr;
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::thread;
+use core::marker::PhantomData;
+use core::ptr;
+use core::task::{Context, Poll, Waker};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::hazard::{HazardRegistry, HazardSlot};
+use crate::port::{spin_hint, Arc, AtomicBool, AtomicPtr, AtomicUsize, Box, Ordering, Vec};
+use crate::spin::SpinMutex;
+#[cfg(feature = "std")]
+use crate::{RecvError, RecvTimeoutError};
+use crate::{SendError, TryRecvError};
 
 /// Creates a [`Sender`] and [`Receiver`] to broadcast messages.
 /// See the module level documentation for more info.
@@ -12,7 +19,20 @@ use std::thread;
 /// [`Sender`]: struct.Sender.html
 /// [`Receiver`]: struct.Receiver.html
 pub fn broadcaster<T: Send + Clone + 'static>() -> (Sender<T>, Receiver<T>) {
-    let channel = Arc::new(BroadcastChannel::new());
+    let channel = Arc::new(BroadcastChannel::new(None));
+    (Sender::new(channel.clone()), Receiver::new(channel))
+}
+
+/// Creates a [`Sender`] and [`Receiver`] like [`broadcaster`], but caps how
+/// far the fastest `Sender` may run ahead of the slowest surviving
+/// `Receiver` to `capacity` nodes.
+///
+/// Once that many unread nodes are outstanding, `Sender::send` blocks (and
+/// `Sender::poll_ready` returns `Poll::Pending`) until the slowest receiver
+/// catches up, bounding the channel's memory use instead of growing
+/// without limit.
+pub fn bounded_broadcaster<T: Send + Clone + 'static>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(BroadcastChannel::new(Some(capacity)));
     (Sender::new(channel.clone()), Receiver::new(channel))
 }
 
@@ -20,70 +40,261 @@ struct BroadcastChannel<T: Send + Clone> {
     head: AtomicPtr<Node<T>>,
     tail: AtomicPtr<Node<T>>,
     readers: AtomicUsize,
-    _data: PhantomData<T>, //hazards: Map<*mut T, AtomicUsize>
+    // Wakers registered by `Receiver::poll_next` while no value was ready.
+    // `send` drains and wakes all of them once a node is linked. A spinlock
+    // rather than `std::sync::Mutex` so this also works under `no_std`.
+    wakers: SpinMutex<Vec<Waker>>,
+    // Number of live `Sender`s; the last one to drop flips `disconnected`.
+    senders: AtomicUsize,
+    disconnected: AtomicBool,
+    // Number of live `Receiver`s; the last one to drop flips
+    // `no_receivers`, which lets `send` fail fast instead of growing the
+    // list for nobody.
+    receivers: AtomicUsize,
+    no_receivers: AtomicBool,
+    // Lets blocking `Receiver::recv`/`recv_timeout` park instead of
+    // busy-spinning on `try_advance`. There's no thread to park under
+    // `no_std`, so this whole mechanism is `std`-only.
+    #[cfg(feature = "std")]
+    recv_lock: std::sync::Mutex<()>,
+    #[cfg(feature = "std")]
+    recv_condvar: std::sync::Condvar,
+    // Backpressure for `bounded_broadcaster`: `None` for an unbounded
+    // channel, where `send` never blocks. `length` counts nodes sent but
+    // not yet fully consumed; senders block in `send_lock`/`send_condvar`
+    // (and register in `send_wakers` for `poll_ready`) until a receiver
+    // frees one up. Under `no_std`, blocking `send` just spins instead.
+    capacity: Option<usize>,
+    length: AtomicUsize,
+    #[cfg(feature = "std")]
+    send_lock: std::sync::Mutex<()>,
+    #[cfg(feature = "std")]
+    send_condvar: std::sync::Condvar,
+    send_wakers: SpinMutex<Vec<Waker>>,
+    // Lets `send` safely dereference a node it just unlinked from `head`,
+    // and `Receiver::try_advance` safely retire a node it just unlinked
+    // from `tail`, without a `Sender`/`Receiver` on another thread racing
+    // a free of that same node out from under the read. See `hazard`.
+    hazards: HazardRegistry<Node<T>>,
+    _data: PhantomData<T>,
 }
 
 impl<T: Send + Clone + 'static> BroadcastChannel<T> {
-    fn new() -> Self {
+    fn new(capacity: Option<usize>) -> Self {
         let channel = Self {
             head: AtomicPtr::new(ptr::null_mut()),
             tail: AtomicPtr::new(ptr::null_mut()),
-            // We create the channel with 1 sender so we can just
-            // set the `readers` to 1 to avoid an additional atomic write
+            // Counts live `Receiver`s *beyond* the one `broadcaster`/
+            // `bounded_broadcaster` hands out up front, which is instead
+            // baked into the sentinel node's hardcoded `readers: 1` below
+            // — so this starts at 0 rather than 1 to avoid double-counting
+            // it, and every node's own reader/unread count is this plus one.
             readers: AtomicUsize::new(0),
+            wakers: SpinMutex::new(Vec::new()),
+            // `broadcaster` hands out exactly one `Sender` and one
+            // `Receiver` up front.
+            senders: AtomicUsize::new(1),
+            disconnected: AtomicBool::new(false),
+            receivers: AtomicUsize::new(1),
+            no_receivers: AtomicBool::new(false),
+            #[cfg(feature = "std")]
+            recv_lock: std::sync::Mutex::new(()),
+            #[cfg(feature = "std")]
+            recv_condvar: std::sync::Condvar::new(),
+            capacity,
+            length: AtomicUsize::new(0),
+            #[cfg(feature = "std")]
+            send_lock: std::sync::Mutex::new(()),
+            #[cfg(feature = "std")]
+            send_condvar: std::sync::Condvar::new(),
+            send_wakers: SpinMutex::new(Vec::new()),
+            hazards: HazardRegistry::new(),
             _data: PhantomData,
         };
         let first = Box::into_raw(Box::new(Node {
             value: None,
             next: AtomicPtr::new(ptr::null_mut()),
             readers: AtomicUsize::new(1),
+            unread: AtomicUsize::new(1),
         }));
         channel.head.store(first, Ordering::SeqCst);
         channel.tail.store(first, Ordering::SeqCst);
         channel
     }
 
-    fn send(&self, value: T) {
+    fn send(&self, value: T, hazard: &HazardSlot<Node<T>>) -> Result<(), SendError<T>> {
+        if self.no_receivers.load(Ordering::SeqCst) {
+            return Err(SendError(value));
+        }
+
+        if let Some(capacity) = self.capacity {
+            if self.wait_for_capacity(capacity).is_err() {
+                return Err(SendError(value));
+            }
+        }
+
+        // `self.readers` counts only receivers beyond the original one (see
+        // the comment in `new`), so the live count each field below starts
+        // at is one more than that. Both fields must start from the same
+        // snapshot — a `Receiver::clone()` landing between two separate
+        // loads here could otherwise leave them mismatched.
+        let live_readers = self.readers.load(Ordering::SeqCst) + 1;
         let node = Box::into_raw(Box::new(Node::<T> {
             value: Some(value),
             next: AtomicPtr::new(ptr::null_mut()),
-            readers: AtomicUsize::new(self.readers.load(Ordering::SeqCst)),
+            readers: AtomicUsize::new(live_readers),
+            unread: AtomicUsize::new(live_readers),
         }));
 
         loop {
             let head = self.head.load(Ordering::SeqCst);
 
-            let old_head = self.head.compare_and_swap(head, node, Ordering::SeqCst);
-            if old_head != head {
-                thread::yield_now();
+            // Publish `head` as about to be dereferenced before touching
+            // it, so a `Receiver` that concurrently retires it (having
+            // raced us to become the last reader of it) defers the free
+            // instead of pulling it out from under the dereference below.
+            hazard.protect(head);
+            if self.head.load(Ordering::SeqCst) != head {
+                // `head` may already have been retired between the load
+                // above and publishing the hazard; re-check before relying
+                // on it being safe to dereference.
+                continue;
+            }
+
+            if self
+                .head
+                .compare_exchange(head, node, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+            {
+                spin_hint();
                 continue;
             }
 
-            // TODO: Add a hazard pointer to fix a possible data race between this value being dropped before we set the next pointer
             if !head.is_null() {
-                // SAFETY: The head always be pointing at a valid node
+                // SAFETY: `head` is hazard-protected above, so it can't have
+                // been freed out from under us even if its reader count
+                // already hit zero.
                 let old_node = unsafe { &*head };
-                // No other threads should be changing this node after we switch the head
-                assert_ne!(
-                    old_node
-                        .next
-                        .compare_and_swap(ptr::null_mut(), node, Ordering::SeqCst),
-                    node
+                // We're the only thread that can ever be linking into this
+                // particular node: it became unreachable from `self.head`
+                // the moment our CAS above succeeded, and nothing else
+                // writes to a node's `next` after it stops being head.
+                let _ = old_node.next.compare_exchange(
+                    ptr::null_mut(),
+                    node,
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
                 );
             }
+            hazard.clear();
             break;
         }
+
+        // Wake any tasks that were parked in `Receiver::poll_next` waiting
+        // for this node to show up.
+        for waker in self.wakers.lock().drain(..) {
+            waker.wake();
+        }
+
+        // Wake any threads blocked in `Receiver::recv`/`recv_timeout`. The
+        // lock/unlock pair (rather than just `notify_all`) is what makes
+        // this race-free: a receiver that is about to wait either already
+        // observed the new node above, or is guaranteed to still be holding
+        // (or about to acquire) `recv_lock`, so this call can't sneak its
+        // notification in before that receiver starts waiting.
+        #[cfg(feature = "std")]
+        {
+            drop(self.recv_lock.lock().unwrap());
+            self.recv_condvar.notify_all();
+        }
+
+        Ok(())
+    }
+
+    /// Blocks until `length` is below `capacity`, then reserves a slot by
+    /// bumping it back up. Returns `Err` if every receiver disconnects
+    /// while we're waiting, since nothing will ever free a slot then.
+    #[cfg(feature = "std")]
+    fn wait_for_capacity(&self, capacity: usize) -> Result<(), ()> {
+        loop {
+            let len = self.length.load(Ordering::SeqCst);
+            if len < capacity {
+                if self
+                    .length
+                    .compare_exchange(len, len + 1, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+                {
+                    return Ok(());
+                }
+                spin_hint();
+                continue;
+            }
+
+            let guard = self.send_lock.lock().unwrap();
+            if self.length.load(Ordering::SeqCst) < capacity {
+                // Room opened up since we checked above; loop back to the
+                // top to reserve it.
+                continue;
+            }
+            if self.no_receivers.load(Ordering::SeqCst) {
+                return Err(());
+            }
+            drop(self.send_condvar.wait(guard).unwrap());
+        }
+    }
+
+    /// `no_std` has no thread to park, so blocking `send` just spins until
+    /// a slot frees up or every receiver disconnects.
+    #[cfg(not(feature = "std"))]
+    fn wait_for_capacity(&self, capacity: usize) -> Result<(), ()> {
+        loop {
+            let len = self.length.load(Ordering::SeqCst);
+            if len < capacity {
+                if self
+                    .length
+                    .compare_exchange(len, len + 1, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_ok()
+                {
+                    return Ok(());
+                }
+            } else if self.no_receivers.load(Ordering::SeqCst) {
+                return Err(());
+            }
+            spin_hint();
+        }
+    }
+
+    /// The non-blocking counterpart to `wait_for_capacity`, used by
+    /// `Sender::poll_ready`. This only *checks* capacity; unlike
+    /// `wait_for_capacity` it doesn't reserve a slot, so the `send` a
+    /// caller makes after seeing `true` can in principle still have to
+    /// wait a moment if another sender won the race for the freed slot.
+    fn poll_capacity(&self, capacity: usize, waker: &Waker) -> bool {
+        if self.length.load(Ordering::SeqCst) < capacity {
+            return true;
+        }
+
+        self.send_wakers.lock().push(waker.clone());
+
+        // A slot may have freed up between the check above and registering
+        // the waker; give it one more look before reporting "not ready".
+        self.length.load(Ordering::SeqCst) < capacity
     }
 }
 
 impl<T: Send + Clone> Drop for BroadcastChannel<T> {
     fn drop(&mut self) {
-        while !(*self.tail.get_mut()).is_null() {
-            let tail = *self.tail.get_mut();
+        // `&mut self` means every `Sender`/`Receiver` (and thus every
+        // hazard slot) is already gone, so the remaining nodes can be
+        // freed directly without going through `hazards.retire`. Plain
+        // `load`s rather than `get_mut` here, since loom's `AtomicPtr`
+        // doesn't expose the latter.
+        let mut tail = self.tail.load(Ordering::SeqCst);
+        while !tail.is_null() {
             unsafe {
-                *self.tail.get_mut() = *(*tail).next.get_mut();
-
-                ptr::drop_in_place(tail);
+                let next = (*tail).next.load(Ordering::SeqCst);
+                drop(Box::from_raw(tail));
+                tail = next;
             }
         }
     }
@@ -92,26 +303,114 @@ impl<T: Send + Clone> Drop for BroadcastChannel<T> {
 struct Node<T: Send + Clone> {
     value: Option<T>,
     next: AtomicPtr<Node<T>>,
+    // Counts receivers that still need to advance away from this node
+    // before it's safe to free (see `Receiver::try_advance`). Distinct
+    // from `unread` below: a receiver can sit on a node, having already
+    // read it, for an arbitrarily long time before advancing again.
     readers: AtomicUsize,
+    // Counts receivers that haven't yet *read* this node's value, so
+    // `bounded_broadcaster` capacity can be released the moment the
+    // slowest one catches up, rather than waiting for every receiver to
+    // additionally advance past it. Unused (and left at its initial
+    // value) for the sentinel node, which never holds a value.
+    unread: AtomicUsize,
 }
 
-#[derive(Clone)]
 pub struct Sender<T: Send + Clone> {
     channel: Arc<BroadcastChannel<T>>,
+    hazard: Arc<HazardSlot<Node<T>>>,
 }
 
 impl<T: Send + Clone + 'static> Sender<T> {
     fn new(channel: Arc<BroadcastChannel<T>>) -> Self {
-        Self { channel }
+        let hazard = channel.hazards.register();
+        Self { channel, hazard }
     }
 
-    pub fn send(&self, value: T) {
-        self.channel.send(value);
+    /// Sends `value` to every [`Receiver`] created before it.
+    ///
+    /// Returns [`SendError`] if every `Receiver` has already been dropped,
+    /// so the producer can stop early instead of filling a channel nobody
+    /// reads.
+    ///
+    /// [`Receiver`]: struct.Receiver.html
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.channel.send(value, &self.hazard)
     }
 
-    pub fn send_all<I: IntoIterator<Item = T>>(&self, into_iter: I) {
+    /// Sends every item of `into_iter`, stopping at the first
+    /// [`SendError`] if all receivers are dropped partway through.
+    pub fn send_all<I: IntoIterator<Item = T>>(&self, into_iter: I) -> Result<(), SendError<T>> {
         for item in into_iter {
-            self.send(item);
+            self.send(item)?;
+        }
+        Ok(())
+    }
+
+    /// The async counterpart to the backpressure [`bounded_broadcaster`]
+    /// applies to [`send`](Sender::send): on a channel created with
+    /// [`broadcaster`] this is always `Poll::Ready(Ok(()))`, but on a
+    /// bounded one it returns `Poll::Pending` (after registering `cx`'s
+    /// waker) while the slowest receiver hasn't caught up enough to leave
+    /// room for another value.
+    ///
+    /// Once this resolves `Ready`, call [`send`](Sender::send) to actually
+    /// deliver the value — as with `tokio`'s bounded channels, a moment can
+    /// still pass between the two if another sender wins the race for the
+    /// freed slot.
+    pub fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), SendError<()>>> {
+        if self.channel.no_receivers.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(SendError(())));
+        }
+
+        match self.channel.capacity {
+            None => Poll::Ready(Ok(())),
+            Some(capacity) => {
+                if self.channel.poll_capacity(capacity, cx.waker()) {
+                    Poll::Ready(Ok(()))
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+impl<T: Send + Clone> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.channel.senders.fetch_add(1, Ordering::SeqCst);
+        Self {
+            channel: self.channel.clone(),
+            // Each `Sender` gets its own slot rather than sharing this
+            // one, since clones are meant to send concurrently from
+            // separate threads.
+            hazard: self.channel.hazards.register(),
+        }
+    }
+}
+
+impl<T: Send + Clone> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.channel.hazards.deregister(&self.hazard);
+        if self.channel.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.channel.disconnected.store(true, Ordering::SeqCst);
+
+            // Wake any tasks/threads parked in `Receiver::poll_next` or
+            // `Select::select` waiting on a value from this channel: with
+            // the last `Sender` gone, none is ever coming, and they'd
+            // otherwise park forever instead of observing the disconnect.
+            for waker in self.channel.wakers.lock().drain(..) {
+                waker.wake();
+            }
+
+            // See the matching comment in `BroadcastChannel::send`: taking
+            // the lock before notifying is what prevents a receiver from
+            // missing this disconnect.
+            #[cfg(feature = "std")]
+            {
+                drop(self.channel.recv_lock.lock().unwrap());
+                self.channel.recv_condvar.notify_all();
+            }
         }
     }
 }
@@ -120,21 +419,37 @@ unsafe impl<T: Send + Clone> Send for Receiver<T> {}
 pub struct Receiver<T: Send + Clone + 'static> {
     current: *mut Node<T>,
     channel: Arc<BroadcastChannel<T>>,
+    hazard: Arc<HazardSlot<Node<T>>>,
 }
 
 impl<T: Send + Clone> Receiver<T> {
     fn new(channel: Arc<BroadcastChannel<T>>) -> Self {
+        let hazard = channel.hazards.register();
         Self {
             current: channel.head.load(Ordering::SeqCst),
             channel,
+            hazard,
         }
     }
 }
 
 impl<T: Send + Clone + 'static> Clone for Receiver<T> {
     fn clone(&self) -> Self {
+        // Each clone gets its own slot: they run independently (often on
+        // separate threads), each advancing `current` at its own pace.
+        let hazard = self.channel.hazards.register();
+
+        // `head` can be retired by a concurrent `Receiver::try_advance` the
+        // moment its last existing reader advances off of it, so it must be
+        // hazard-protected before we dereference it below, the same way
+        // `send` protects the node it links after.
         let head = loop {
             let old_head = self.channel.head.load(Ordering::SeqCst);
+            hazard.protect(old_head);
+            if self.channel.head.load(Ordering::SeqCst) != old_head {
+                continue;
+            }
+
             self.channel.readers.fetch_add(1, Ordering::SeqCst);
             if old_head != self.channel.head.load(Ordering::SeqCst) {
                 self.channel.readers.fetch_sub(1, Ordering::SeqCst);
@@ -143,44 +458,327 @@ impl<T: Send + Clone + 'static> Clone for Receiver<T> {
             }
         };
 
+        // The new `Receiver` starts out sitting on `head`, just like every
+        // receiver that has already advanced up to it, so `head`'s reader
+        // count must include it too — otherwise one of those already there
+        // could advance past `head` and free it while this receiver still
+        // refers to it. `head`'s `unread` count is untouched: this receiver
+        // starts past `head`, so it will never read its value.
+        unsafe { &*head }.readers.fetch_add(1, Ordering::SeqCst);
+        hazard.clear();
+
+        self.channel.receivers.fetch_add(1, Ordering::SeqCst);
+
         Self {
             current: head,
+            hazard,
             channel: self.channel.clone(),
         }
     }
 }
 
-impl<T: Send + Clone + 'static> Iterator for Receiver<T> {
-    type Item = T;
+impl<T: Send + Clone> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.channel.hazards.deregister(&self.hazard);
 
-    fn next(&mut self) -> Option<T> {
+        // This receiver is never going to read anything past `current`, so
+        // release every claim it was seeded with instead of leaving it
+        // stuck forever: a `readers` claim on `current` and on every node
+        // already linked after it (nothing has advanced past any of them
+        // yet), plus an `unread` claim on each of those that holds a value,
+        // mirroring what `try_advance` releases one node at a time as a
+        // receiver actually reads through them. Left unreleased, a node's
+        // `unread` count could never reach zero, permanently wedging
+        // `bounded_broadcaster` capacity, and its `readers` count could
+        // never reach zero either, leaking every node sent from here on for
+        // the rest of the channel's life.
+        //
+        // No hazard protection is needed to walk this chain: every node
+        // visited is one this receiver already holds an unreleased
+        // `readers` claim on, which is exactly what keeps it from being
+        // retired out from under this loop (see the matching comment in
+        // `try_advance`).
+        let mut node = self.current;
+        loop {
+            let next = unsafe { &*node }.next.load(Ordering::SeqCst);
+
+            if node != self.current
+                && unsafe { &*node }.unread.fetch_sub(1, Ordering::SeqCst) == 1
+                && unsafe { &*node }.value.is_some()
+                && self.channel.capacity.is_some()
+            {
+                self.channel.length.fetch_sub(1, Ordering::SeqCst);
+                for waker in self.channel.send_wakers.lock().drain(..) {
+                    waker.wake();
+                }
+                #[cfg(feature = "std")]
+                {
+                    drop(self.channel.send_lock.lock().unwrap());
+                    self.channel.send_condvar.notify_all();
+                }
+            }
+
+            // A node with no successor yet may still be `head`, the point
+            // future `send`s link onto, so — just like `try_advance` —
+            // this never retires one, even if this was its last reader.
+            if unsafe { &*node }.readers.fetch_sub(1, Ordering::SeqCst) == 1 && !next.is_null() {
+                unsafe { self.channel.hazards.retire(node) };
+                let _ =
+                    self.channel
+                        .tail
+                        .compare_exchange(node, next, Ordering::SeqCst, Ordering::SeqCst);
+            }
+
+            if next.is_null() {
+                break;
+            }
+            node = next;
+        }
+
+        if self.channel.receivers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.channel.no_receivers.store(true, Ordering::SeqCst);
+            // A sender stuck in `wait_for_capacity` would otherwise block
+            // forever with no receiver left to ever free a slot; wake it
+            // so it can observe `no_receivers` and fail.
+            #[cfg(feature = "std")]
+            {
+                drop(self.channel.send_lock.lock().unwrap());
+                self.channel.send_condvar.notify_all();
+            }
+        } else {
+            // Mirrors the `fetch_add` in `clone`: this receiver no longer
+            // exists to be seeded into nodes sent from now on, so it must
+            // stop being counted in the `+ 1` `send` uses to size them, or
+            // those nodes would carry a phantom reader nothing will ever
+            // release.
+            self.channel.readers.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl<T: Send + Clone + 'static> Receiver<T> {
+    /// Reports whether a node has already been linked after `current`,
+    /// i.e. whether `try_advance`/`try_recv` would return a value right
+    /// now. Used by the [`select`](crate::select) module to scan several
+    /// receivers without consuming from any but the chosen one.
+    #[cfg(feature = "std")]
+    pub(crate) fn has_ready(&self) -> bool {
+        !unsafe { &*self.current }
+            .next
+            .load(Ordering::SeqCst)
+            .is_null()
+    }
+
+    /// Whether every `Sender` for this channel has been dropped.
+    #[cfg(feature = "std")]
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.channel.disconnected.load(Ordering::SeqCst)
+    }
+
+    /// Registers `waker` to be woken the next time this channel links a
+    /// new node, mirroring what `poll_next` does for a single receiver.
+    #[cfg(feature = "std")]
+    pub(crate) fn register_waker(&self, waker: Waker) {
+        self.channel.wakers.lock().push(waker);
+    }
+
+    /// Returns `None` if no new value has been sent since the last call.
+    fn try_advance(&mut self) -> Option<T> {
         let old = self.current;
+        // `old` is always a node this `Receiver` is already counted among
+        // the readers of (see the refcounting below), so it can't have
+        // been retired yet; no hazard needed to read its `next`.
         let current = unsafe { &*old }.next.load(Ordering::SeqCst);
         if current.is_null() {
             return None;
         }
+
+        // `current` isn't ours to read yet until we protect it: retire
+        // only frees a node once no hazard slot points at it, so this
+        // keeps it alive even if its reader count is concurrently driven
+        // to zero by another `Receiver` while we're still cloning its
+        // value below.
+        self.hazard.protect(current);
+
         self.current = current;
         let value = unsafe { &*current }.value.clone();
+
+        // A slot is released for `bounded_broadcaster` the moment every
+        // receiver that existed when `current` was sent has read it, not
+        // whenever they additionally advance past it — a receiver can sit
+        // on a freshly read node for an arbitrary amount of time before
+        // its next call, and `send` shouldn't have to wait for that too.
+        // This must happen while `current` is still hazard-protected: it
+        // dereferences `current`, and clearing the hazard first would let
+        // another receiver's concurrent advance retire and free it first.
+        if unsafe { &*current }.unread.fetch_sub(1, Ordering::SeqCst) == 1
+            && unsafe { &*current }.value.is_some()
+            && self.channel.capacity.is_some()
+        {
+            self.channel.length.fetch_sub(1, Ordering::SeqCst);
+            for waker in self.channel.send_wakers.lock().drain(..) {
+                waker.wake();
+            }
+            #[cfg(feature = "std")]
+            {
+                drop(self.channel.send_lock.lock().unwrap());
+                self.channel.send_condvar.notify_all();
+            }
+        }
+        self.hazard.clear();
+
         unsafe {
             if (*old).readers.fetch_sub(1, Ordering::SeqCst) == 1 {
-                ptr::drop_in_place(old);
-                self.channel
-                    .tail
-                    .compare_and_swap(old, current, Ordering::SeqCst);
+                self.channel.hazards.retire(old);
+                let _ = self.channel.tail.compare_exchange(
+                    old,
+                    current,
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                );
             }
         }
         value
     }
+
+    /// Polls the receiver for a value, the `async` counterpart to
+    /// `Iterator::next`.
+    ///
+    /// If no value is ready yet, the current task's waker is registered so
+    /// that it is woken up the next time a [`Sender`] delivers a value, and
+    /// `Poll::Pending` is returned. This lets a `Receiver` be driven inside
+    /// an async task (e.g. as a `futures::Stream`) instead of busy-spinning
+    /// on `Iterator::next`. Once every [`Sender`] has been dropped and no
+    /// value is left, it returns `Poll::Ready(None)`.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    pub fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        match self.try_recv() {
+            Ok(value) => return Poll::Ready(Some(value)),
+            Err(TryRecvError::Disconnected) => return Poll::Ready(None),
+            Err(TryRecvError::Empty) => {}
+        }
+
+        self.channel.wakers.lock().push(cx.waker().clone());
+
+        // A value (or the last `Sender::drop`) may have landed between our
+        // first check and registering the waker above; re-check once more
+        // so we don't miss the wakeup for it.
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Some(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+
+    /// Returns a value if one is ready, without blocking.
+    ///
+    /// Unlike `Iterator::next`, this distinguishes "nothing sent yet" from
+    /// "channel empty and disconnected" via [`TryRecvError`].
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        if let Some(value) = self.try_advance() {
+            return Ok(value);
+        }
+
+        if self.channel.disconnected.load(Ordering::SeqCst) {
+            // A last value may have raced the final `Sender::drop`; give
+            // `try_advance` one more look before declaring it over.
+            return self.try_advance().ok_or(TryRecvError::Disconnected);
+        }
+
+        Err(TryRecvError::Empty)
+    }
+
+    /// Blocks the current thread until a value is available, or returns
+    /// [`RecvError`] once every [`Sender`] has been dropped with nothing
+    /// left to deliver.
+    ///
+    /// [`Sender`]: struct.Sender.html
+    #[cfg(feature = "std")]
+    pub fn recv(&mut self) -> Result<T, RecvError> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            let guard = self.channel.recv_lock.lock().unwrap();
+            let has_next = !unsafe { &*self.current }
+                .next
+                .load(Ordering::SeqCst)
+                .is_null();
+            if has_next {
+                // Drop the lock and let the top of the loop pick it up via
+                // `try_recv`.
+                continue;
+            }
+            if self.channel.disconnected.load(Ordering::SeqCst) {
+                return Err(RecvError);
+            }
+            drop(self.channel.recv_condvar.wait(guard).unwrap());
+        }
+    }
+
+    /// Like [`recv`], but gives up and returns
+    /// [`RecvTimeoutError::Timeout`] if no value arrives within `timeout`.
+    ///
+    /// [`recv`]: #method.recv
+    #[cfg(feature = "std")]
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+
+            let guard = self.channel.recv_lock.lock().unwrap();
+            let has_next = !unsafe { &*self.current }
+                .next
+                .load(Ordering::SeqCst)
+                .is_null();
+            if has_next {
+                continue;
+            }
+            if self.channel.disconnected.load(Ordering::SeqCst) {
+                return Err(RecvTimeoutError::Disconnected);
+            }
+            drop(
+                self.channel
+                    .recv_condvar
+                    .wait_timeout(guard, deadline.saturating_duration_since(now))
+                    .unwrap(),
+            );
+        }
+    }
+}
+
+impl<T: Send + Clone + 'static> Iterator for Receiver<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.try_recv().ok()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "std")]
+    use std::thread;
+
     #[test]
     fn sending() {
         let (tx, mut rx) = broadcaster();
-        tx.send(1);
-        tx.send(2);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
 
         assert_eq!(Some(1), rx.next());
         assert_eq!(Some(2), rx.next());
@@ -189,7 +787,7 @@ mod tests {
     #[test]
     fn send_all() {
         let (tx, mut rx) = broadcaster();
-        tx.send_all(0..1000);
+        tx.send_all(0..1000).unwrap();
         for i in 0..1000 {
             assert_eq!(rx.next(), Some(i));
         }
@@ -199,10 +797,10 @@ mod tests {
     fn multiple_receivers() {
         let (tx, mut rx) = broadcaster();
         let mut rx2 = rx.clone();
-        tx.send(1);
-        tx.send(2);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
         let mut rx3 = rx.clone();
-        tx.send(3);
+        tx.send(3).unwrap();
         assert_eq!(Some(1), rx.next());
         assert_eq!(Some(2), rx.next());
         assert_eq!(Some(3), rx.next());
@@ -221,10 +819,150 @@ mod tests {
     fn multiple_senders() {
         let (tx, rx) = broadcaster();
         let tx2 = tx.clone();
-        tx.send_all(0..5);
-        tx2.send_all(5..10);
+        tx.send_all(0..5).unwrap();
+        tx2.send_all(5..10).unwrap();
         for (i, item) in rx.enumerate() {
             assert_eq!(i, item as usize);
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn bounded_send_blocks_until_read() {
+        let (tx, mut rx) = bounded_broadcaster(2);
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+
+        let tx2 = tx.clone();
+        let sender = thread::spawn(move || {
+            // With capacity 2 already outstanding, this would block
+            // forever if nothing reads `rx` to free a slot.
+            tx2.send(3).unwrap();
+        });
+
+        assert_eq!(rx.next(), Some(1));
+        sender.join().unwrap();
+
+        assert_eq!(rx.next(), Some(2));
+        assert_eq!(rx.next(), Some(3));
+        assert_eq!(rx.next(), None);
+    }
+
+    #[test]
+    fn bounded_send_fails_once_receivers_are_gone() {
+        let (tx, rx) = bounded_broadcaster(1);
+        tx.send(1).unwrap();
+        drop(rx);
+
+        assert!(tx.send(2).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn poll_next_wakes_once_a_value_is_sent_after_registering() {
+        use std::sync::Arc;
+        use std::task::Wake;
+
+        struct ThreadWaker(thread::Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+
+            fn wake_by_ref(self: &Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let (tx, mut rx) = broadcaster::<i32>();
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        // Nothing sent yet: registers our waker and returns `Pending`.
+        assert_eq!(rx.poll_next(&mut cx), Poll::Pending);
+
+        let sender = thread::spawn(move || {
+            tx.send(42).unwrap();
+        });
+
+        loop {
+            match rx.poll_next(&mut cx) {
+                Poll::Ready(value) => {
+                    assert_eq!(value, Some(42));
+                    break;
+                }
+                Poll::Pending => thread::park(),
+            }
+        }
+        sender.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn recv_blocks_until_a_value_is_sent() {
+        let (tx, mut rx) = broadcaster();
+        let sender = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(20));
+            tx.send(5).unwrap();
+        });
+
+        assert_eq!(rx.recv(), Ok(5));
+        sender.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn recv_errors_once_every_sender_drops_while_parked() {
+        let (tx, mut rx) = broadcaster::<i32>();
+        let dropper = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(20));
+            drop(tx);
+        });
+
+        assert_eq!(rx.recv(), Err(RecvError));
+        dropper.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn recv_timeout_times_out_when_nothing_is_sent() {
+        let (_tx, mut rx) = broadcaster::<i32>();
+        assert_eq!(
+            rx.recv_timeout(std::time::Duration::from_millis(20)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn recv_timeout_errors_once_every_sender_drops_while_parked() {
+        let (tx, mut rx) = broadcaster::<i32>();
+        let dropper = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(20));
+            drop(tx);
+        });
+
+        assert_eq!(
+            rx.recv_timeout(std::time::Duration::from_secs(5)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+        dropper.join().unwrap();
+    }
+
+    #[test]
+    fn try_recv_distinguishes_empty_from_disconnected() {
+        let (tx, mut rx) = broadcaster::<i32>();
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        drop(tx);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn send_fails_once_every_receiver_is_dropped() {
+        let (tx, rx) = broadcaster();
+        drop(rx);
+
+        assert!(matches!(tx.send(1), Err(SendError(1))));
+    }
 }
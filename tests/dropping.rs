@@ -3,7 +3,7 @@ use broadcast_channel::*;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 #[derive(Clone)]
-struct SideEffectDrop(u32, &'static AtomicU32);
+struct SideEffectDrop(#[allow(dead_code)] u32, &'static AtomicU32);
 impl Drop for SideEffectDrop {
     fn drop(&mut self) {
         self.1.fetch_add(1, Ordering::SeqCst);
@@ -15,7 +15,7 @@ fn dropping() {
     static DROPS: AtomicU32 = AtomicU32::new(0);
     let (tx, rx) = broadcaster();
     for _ in 0..10 {
-        tx.send(SideEffectDrop(1, &DROPS));
+        tx.send(SideEffectDrop(1, &DROPS)).unwrap();
     }
     drop(tx);
     drop(rx);
@@ -27,7 +27,7 @@ fn dropping_after_recv() {
     static DROPS: AtomicU32 = AtomicU32::new(0);
     let (tx, mut rx) = broadcaster();
     for _ in 0..10 {
-        tx.send(SideEffectDrop(1, &DROPS));
+        tx.send(SideEffectDrop(1, &DROPS)).unwrap();
     }
 
     rx.nth(2);
@@ -37,3 +37,50 @@ fn dropping_after_recv() {
     // We drop the 10 put in the channel + 3 that are read from the receiver
     assert_eq!(DROPS.load(Ordering::SeqCst), 13);
 }
+
+#[test]
+fn dropping_a_non_reading_clone_still_lets_unbounded_nodes_free() {
+    static DROPS: AtomicU32 = AtomicU32::new(0);
+    let (tx, mut rx) = broadcaster();
+
+    // A clone that's dropped without ever reading anything must not leave
+    // behind a claim every later node carries forever: if it did, none of
+    // these sends would ever be freed for the rest of the channel's life,
+    // and every read below would be the only source of drops.
+    drop(rx.clone());
+
+    for _ in 0..10 {
+        tx.send(SideEffectDrop(1, &DROPS)).unwrap();
+    }
+    for _ in &mut rx {
+        // Drain everything `rx` can see; only the dropped clone's claim
+        // (if left outstanding) could still be keeping these alive.
+    }
+
+    // 9 of the 10 nodes are freed as `rx` advances past them (the 10th
+    // stays alive, pinned by `rx.current`, until `rx` itself drops), plus
+    // one more drop for each of the 10 values `rx` read out. Left at just
+    // 10 (only the reads, no frees at all) would mean the dropped clone's
+    // claim on each node was never released, and `tx`/`rx` staying alive
+    // would keep every node around for the rest of the channel's life.
+    assert_eq!(DROPS.load(Ordering::SeqCst), 19);
+}
+
+#[test]
+fn dropping_a_non_reading_clone_releases_bounded_capacity() {
+    let (tx, mut rx) = bounded_broadcaster(1);
+
+    // Unlike the clone above, this one starts existing before the node
+    // below is sent, so that node's `unread` count is seeded expecting a
+    // read from both `rx` and the clone.
+    let rx2 = rx.clone();
+    tx.send(1).unwrap();
+    drop(rx2);
+
+    // `rx` reading the node releases its own share of `unread`, but not
+    // the clone's; without the clone's drop also releasing its share,
+    // this second send would block forever waiting for a slot that can
+    // never come back.
+    assert_eq!(rx.next(), Some(1));
+    tx.send(2).unwrap();
+}
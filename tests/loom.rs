@@ -0,0 +1,47 @@
+//! Loom model checks for the lock-free send/receive CAS protocol in
+//! `BroadcastChannel`. Only runs under `RUSTFLAGS="--cfg loom" cargo test
+//! --test loom`, since exhaustively interleaving even these small scenarios
+//! is far too slow to run as part of the normal test suite.
+
+#![cfg(loom)]
+
+use broadcast_channel::broadcaster;
+use loom::thread;
+
+#[test]
+fn one_sender_two_receivers() {
+    loom::model(|| {
+        let (tx, rx) = broadcaster();
+        let mut rx2 = rx.clone();
+
+        tx.send(1).unwrap();
+        tx.send(2).unwrap();
+
+        let mut rx = rx;
+        assert_eq!(rx.next(), Some(1));
+        assert_eq!(rx.next(), Some(2));
+        assert_eq!(rx2.next(), Some(1));
+        assert_eq!(rx2.next(), Some(2));
+    });
+}
+
+#[test]
+fn concurrent_send_and_clone() {
+    loom::model(|| {
+        let (tx, rx) = broadcaster();
+        let tx2 = tx.clone();
+
+        let sender = thread::spawn(move || {
+            tx2.send(1).unwrap();
+        });
+
+        // Racing the clone against the in-flight send exercises the
+        // `readers`-bumping retry loop in `Receiver::clone` against the
+        // `head` CAS loop that `send` uses to link a node.
+        let rx2 = rx.clone();
+        sender.join().unwrap();
+
+        drop(rx);
+        drop(rx2);
+    });
+}
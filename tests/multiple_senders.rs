@@ -11,7 +11,7 @@ fn ten_senders() {
         let tx = tx.clone();
         let thread = thread::spawn(move || {
             for _ in 0..nums_pushed {
-                tx.send(1);
+                tx.send(1).unwrap();
             }
         });
         threads.push(thread);
@@ -39,7 +39,7 @@ fn ten_senders_ten_receivers() {
     for _ in 0..thread_num {
         let tx = tx.clone();
         let thread = thread::spawn(move || {
-            tx.send_all(iter::repeat(1).take(nums_pushed));
+            tx.send_all(iter::repeat_n(1, nums_pushed)).unwrap();
         });
         threads.push(thread);
     }
@@ -0,0 +1,45 @@
+//! Tests for the optional `futures::Stream` impl on `Receiver`. Requires
+//! `--features futures`, the same way `tests/loom.rs` requires `--cfg loom`.
+#![cfg(feature = "futures")]
+
+use broadcast_channel::broadcaster;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+
+struct ThreadWaker(thread::Thread);
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+#[test]
+fn stream_delivers_a_value_sent_after_poll_registers_its_waker() {
+    let (tx, mut rx) = broadcaster::<i32>();
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(Pin::new(&mut rx).poll_next(&mut cx), Poll::Pending);
+
+    let sender = thread::spawn(move || {
+        tx.send(7).unwrap();
+    });
+
+    loop {
+        match Pin::new(&mut rx).poll_next(&mut cx) {
+            Poll::Ready(value) => {
+                assert_eq!(value, Some(7));
+                break;
+            }
+            Poll::Pending => thread::park(),
+        }
+    }
+    sender.join().unwrap();
+}